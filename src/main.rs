@@ -1,11 +1,15 @@
+mod money;
 mod payment;
 mod person;
 
 use std::collections::HashMap;
+use std::io::Read as _;
 
 use clap::Parser;
+use rust_decimal::prelude::ToPrimitive;
 use rust_decimal::Decimal;
 
+use money::{Currency, Money, RoundStrategy};
 use payment::Payments;
 use person::Person;
 
@@ -28,16 +32,57 @@ struct Args {
     /// Lista dos gastos individuais no formato NOME=VALOR
     ///
     /// Cada pagamento deve ser informado como nome da pessoa seguido de
-    /// igual e o valor pago.
+    /// igual e o valor pago. Um sufixo de moeda (BRL, USD ou JPY) pode ser
+    /// anexado ao valor; sem sufixo, o valor é tratado na moeda base.
     ///
     /// Exemplos:
     ///   Rafael=50.00 Maria=30.50 "Ana Clara"=100
+    ///   Rafael=50.00USD Maria=30.50
     #[arg(
-        required = true,
+        required_unless_present = "file",
         value_parser = parse_key_val,
         value_name = "NOME=VALOR"
     )]
-    initial_payments: Vec<(String, Decimal)>,
+    initial_payments: Vec<(String, Decimal, Currency)>,
+
+    /// Lê a lista de gastos a partir de um arquivo CSV em vez de (ou além
+    /// de) argumentos posicionais.
+    ///
+    /// O arquivo deve ter um cabeçalho com as colunas `nome,valor` e,
+    /// opcionalmente, `moeda`. Use `-` para ler da entrada padrão.
+    #[arg(
+        short = 'a',
+        long = "arquivo",
+        visible_alias = "file",
+        value_name = "CAMINHO"
+    )]
+    file: Option<String>,
+
+    /// Tabela de conversão entre moedas estrangeiras e a moeda base.
+    ///
+    /// Cada entrada mapeia uma moeda para a taxa de conversão em relação à
+    /// moeda base (aquela usada nos valores sem sufixo de moeda). É
+    /// obrigatória sempre que a conta misturar moedas diferentes.
+    ///
+    /// Exemplo: --taxa USD=5.20
+    #[arg(long = "taxa", visible_alias = "rate", value_name = "MOEDA=TAXA", value_parser = parse_rate)]
+    rates: Vec<(Currency, Decimal)>,
+
+    /// Estratégia usada ao arredondar valores para a menor unidade da moeda.
+    ///
+    /// Útil ao reconciliar com um livro-razão externo que usa uma
+    /// convenção de arredondamento diferente da padrão (metade para longe
+    /// de zero).
+    ///
+    /// Valores aceitos: banker, up, down, half-up (padrão).
+    #[arg(
+        long = "arredondamento",
+        visible_alias = "rounding",
+        value_name = "MODO",
+        default_value = "half-up",
+        value_parser = parse_rounding
+    )]
+    rounding: RoundStrategy,
 
     /// Exporta o resultado no formato Graphviz DOT.
     ///
@@ -48,17 +93,58 @@ struct Args {
     graphviz: bool,
 }
 
+// A macro do `human_panic` ainda referencia o alias depreciado `PanicInfo`;
+// a depreciação é interna à dependência, não ao nosso código.
+#[allow(deprecated)]
 fn main() {
     human_panic::setup_panic!();
 
     let args = Args::parse();
 
-    let initial_payments = args.initial_payments;
+    let mut initial_payments = args.initial_payments;
+    if let Some(path) = &args.file {
+        match read_expenses_csv(path) {
+            Ok(rows) => initial_payments.extend(rows),
+            Err(err) => {
+                eprintln!("Erro ao ler arquivo de gastos: {err}");
+                std::process::exit(1);
+            }
+        }
+    }
+
     let total_persons = args.total_persons.unwrap_or(initial_payments.len());
 
+    let base_currency = Currency::default();
+    let rounding = args.rounding;
+    let rates: HashMap<Currency, Decimal> = args.rates.into_iter().collect();
+
     let mut initial_payments_map = HashMap::new();
-    for (name, value) in initial_payments {
-        *initial_payments_map.entry(name).or_insert(Decimal::ZERO) += value;
+    for (name, value, currency) in initial_payments {
+        let native = Money::with_rounding(value.to_f64().unwrap_or_default(), currency, rounding);
+
+        let value_in_base = if currency == base_currency {
+            native
+        } else if let Some(rate) = rates.get(&currency) {
+            Money::with_rounding(
+                native.decimal() * rate.to_f64().unwrap_or_default(),
+                base_currency,
+                rounding,
+            )
+        } else {
+            eprintln!(
+                "Erro: a conta não fecha! \"{name}\" pagou em {}, mas nenhuma taxa de conversão foi informada.",
+                currency.code()
+            );
+            eprintln!(
+                "Dica: informe a taxa de conversão com --taxa {}=<TAXA>.",
+                currency.code()
+            );
+            std::process::exit(1);
+        };
+
+        *initial_payments_map
+            .entry(name)
+            .or_insert_with(|| Money::new(0., base_currency)) += value_in_base;
     }
 
     if initial_payments_map.len() > total_persons {
@@ -80,7 +166,7 @@ fn main() {
 
     let mut persons: Vec<_> = initial_payments_map
         .iter()
-        .map(|p| Person::named(&p.0, *p.1))
+        .map(|(name, money_spent)| Person::named(name, *money_spent))
         .collect();
 
     let remaining = total_persons - initial_payments_map.len();
@@ -99,11 +185,217 @@ fn main() {
 }
 
 /// Parser customizado para `clap` que transforma uma string "NOME=VALOR"
-/// em uma tupla `(String, Decimal)`.
-fn parse_key_val(s: &str) -> Result<(String, Decimal), String> {
+/// em uma tupla `(String, Decimal, Currency)`.
+///
+/// `VALOR` aceita um sufixo opcional de moeda (ex: `50.00USD`); quando
+/// ausente, a moeda é [`Currency::default`].
+fn parse_key_val(s: &str) -> Result<(String, Decimal, Currency), String> {
     let (k, v) = s.split_once('=').ok_or("use o formato NOME=VALOR")?;
+    let (value, currency) = parse_money_value(v)?;
+    Ok((k.into(), value, currency))
+}
+
+/// Separa a parte numérica do sufixo de moeda de um valor informado na CLI.
+fn parse_money_value(s: &str) -> Result<(Decimal, Currency), String> {
+    let split_at = s.find(|c: char| c.is_ascii_alphabetic());
+    let (value, currency) = match split_at {
+        Some(idx) => (&s[..idx], &s[idx..]),
+        None => (s, ""),
+    };
+
+    let value = value
+        .parse()
+        .map_err(|_| format!("número inválido: {value}"))?;
+    let currency = if currency.is_empty() {
+        Currency::default()
+    } else {
+        currency.parse()?
+    };
+
+    Ok((value, currency))
+}
+
+/// Parser customizado para `clap` que transforma o nome de uma estratégia
+/// de arredondamento em um [`RoundStrategy`].
+fn parse_rounding(s: &str) -> Result<RoundStrategy, String> {
+    s.parse()
+}
+
+/// Parser customizado para `clap` que transforma uma string "MOEDA=TAXA"
+/// em uma tupla `(Currency, Decimal)`.
+fn parse_rate(s: &str) -> Result<(Currency, Decimal), String> {
+    let (currency, rate) = s.split_once('=').ok_or("use o formato MOEDA=TAXA")?;
     Ok((
-        k.into(),
-        v.parse().map_err(|_| format!("número inválido: {v}"))?,
+        currency.parse()?,
+        rate.parse().map_err(|_| format!("taxa inválida: {rate}"))?,
     ))
 }
+
+/// Lê uma lista de gastos a partir de um CSV com cabeçalho `nome,valor`
+/// (e, opcionalmente, `moeda`), produzindo as mesmas tuplas geradas por
+/// [`parse_key_val`]. Use `path == "-"` para ler da entrada padrão.
+fn read_expenses_csv(path: &str) -> Result<Vec<(String, Decimal, Currency)>, String> {
+    let content = if path == "-" {
+        let mut buf = String::new();
+        std::io::stdin()
+            .read_to_string(&mut buf)
+            .map_err(|e| format!("não foi possível ler a entrada padrão: {e}"))?;
+        buf
+    } else {
+        std::fs::read_to_string(path).map_err(|e| format!("não foi possível ler \"{path}\": {e}"))?
+    };
+
+    let mut lines = content.lines();
+    let header = lines
+        .next()
+        .ok_or("arquivo vazio, esperado um cabeçalho \"nome,valor\"")?;
+    let columns: Vec<&str> = header.split(',').map(str::trim).collect();
+
+    let nome_idx = columns
+        .iter()
+        .position(|c| c.eq_ignore_ascii_case("nome"))
+        .ok_or("cabeçalho deve conter a coluna \"nome\"")?;
+    let valor_idx = columns
+        .iter()
+        .position(|c| c.eq_ignore_ascii_case("valor"))
+        .ok_or("cabeçalho deve conter a coluna \"valor\"")?;
+    let moeda_idx = columns.iter().position(|c| c.eq_ignore_ascii_case("moeda"));
+
+    let mut rows = Vec::new();
+    for (i, line) in lines.enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let line_number = i + 2; // +1 pelo cabeçalho, +1 por ser 1-indexado
+
+        let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+
+        let nome = fields
+            .get(nome_idx)
+            .ok_or(format!("linha {line_number}: coluna \"nome\" ausente"))?
+            .to_string();
+        let valor: Decimal = fields
+            .get(valor_idx)
+            .ok_or(format!("linha {line_number}: coluna \"valor\" ausente"))?
+            .parse()
+            .map_err(|_| format!("linha {line_number}: valor inválido"))?;
+        let moeda = match moeda_idx.and_then(|idx| fields.get(idx)) {
+            Some(code) if !code.is_empty() => code
+                .parse()
+                .map_err(|e| format!("linha {line_number}: {e}"))?,
+            _ => Currency::default(),
+        };
+
+        rows.push((nome, valor, moeda));
+    }
+
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    #[test]
+    fn parse_money_value_without_currency_suffix_uses_default() {
+        let (value, currency) = parse_money_value("50.00").unwrap();
+        assert_eq!(value, Decimal::new(5000, 2));
+        assert_eq!(currency, Currency::default());
+    }
+
+    #[test]
+    fn parse_money_value_with_currency_suffix() {
+        let (value, currency) = parse_money_value("50.00USD").unwrap();
+        assert_eq!(value, Decimal::new(5000, 2));
+        assert_eq!(currency, Currency::Usd);
+    }
+
+    #[test]
+    fn parse_money_value_rejects_invalid_number() {
+        assert!(parse_money_value("abcUSD").is_err());
+    }
+
+    #[test]
+    fn parse_money_value_rejects_unknown_currency() {
+        assert!(parse_money_value("50XYZ").is_err());
+    }
+
+    #[test]
+    fn parse_key_val_splits_name_and_value() {
+        let (name, value, currency) = parse_key_val("Rafael=50.00USD").unwrap();
+        assert_eq!(name, "Rafael");
+        assert_eq!(value, Decimal::new(5000, 2));
+        assert_eq!(currency, Currency::Usd);
+    }
+
+    #[test]
+    fn parse_key_val_requires_equals_sign() {
+        assert!(parse_key_val("Rafael50.00").is_err());
+    }
+
+    #[test]
+    fn parse_rate_parses_currency_and_rate() {
+        let (currency, rate) = parse_rate("USD=5.20").unwrap();
+        assert_eq!(currency, Currency::Usd);
+        assert_eq!(rate, Decimal::new(520, 2));
+    }
+
+    #[test]
+    fn parse_rate_requires_equals_sign() {
+        assert!(parse_rate("USD5.20").is_err());
+    }
+
+    #[test]
+    fn read_expenses_csv_parses_rows_with_and_without_currency() {
+        let path = write_temp_csv("nome,valor,moeda\nRafael,50.00,USD\nMaria,30.50,\n");
+        let rows = read_expenses_csv(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(
+            rows,
+            vec![
+                ("Rafael".to_string(), Decimal::new(5000, 2), Currency::Usd),
+                ("Maria".to_string(), Decimal::new(3050, 2), Currency::default()),
+            ]
+        );
+    }
+
+    #[test]
+    fn read_expenses_csv_requires_nome_and_valor_columns() {
+        let path = write_temp_csv("nome\nRafael\n");
+        let err = read_expenses_csv(&path).unwrap_err();
+        std::fs::remove_file(&path).unwrap();
+        assert!(err.contains("valor"));
+    }
+
+    #[test]
+    fn read_expenses_csv_skips_blank_lines() {
+        let path = write_temp_csv("nome,valor\nRafael,50.00\n\nMaria,30.50\n");
+        let rows = read_expenses_csv(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(rows.len(), 2);
+    }
+
+    #[test]
+    fn parse_rounding_accepts_known_modes() {
+        assert_eq!(parse_rounding("half-up").unwrap(), RoundStrategy::HalfUp);
+        assert_eq!(parse_rounding("banker").unwrap(), RoundStrategy::Banker);
+        assert_eq!(parse_rounding("up").unwrap(), RoundStrategy::Up);
+        assert_eq!(parse_rounding("down").unwrap(), RoundStrategy::Down);
+    }
+
+    #[test]
+    fn parse_rounding_rejects_unknown_mode() {
+        assert!(parse_rounding("nearest").is_err());
+    }
+
+    /// Escreve `content` em um arquivo temporário único e devolve seu caminho.
+    fn write_temp_csv(content: &str) -> String {
+        static COUNTER: AtomicU32 = AtomicU32::new(0);
+        let id = COUNTER.fetch_add(1, Ordering::Relaxed);
+        let path = std::env::temp_dir().join(format!("rateio_test_{}_{id}.csv", std::process::id()));
+        std::fs::write(&path, content).unwrap();
+        path.to_str().unwrap().to_string()
+    }
+}
@@ -1,18 +1,334 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
 use std::fmt;
 use std::iter::Sum;
 use std::ops::{Add, AddAssign, Div, DivAssign, Mul, Sub};
+use std::str::FromStr;
 
-/// Representa um valor monetário em centavos.
-///
-/// `Money` armazena valores monetários como inteiros (1 décimo de centavos) para evitar
-/// problemas de precisão de ponto flutuante em cálculos financeiros.
+/// Moeda de um valor monetário, com a quantidade de casas decimais da
+/// sua menor unidade (ex: centavos).
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct Money(usize);
+pub enum Currency {
+    /// Real brasileiro.
+    Brl,
+    /// Dólar americano.
+    Usd,
+    /// Iene japonês (não possui subdivisão em centavos).
+    Jpy,
+    /// Moeda não especificada, com precisão de décimos de centavo.
+    ///
+    /// É a moeda usada quando nenhum sufixo é informado na CLI, preservando
+    /// o comportamento original da ferramenta (1/1000 de unidade).
+    Generic,
+}
+
+impl Currency {
+    /// Quantidade de casas decimais da menor unidade desta moeda, usada
+    /// para a escala de armazenamento interno.
+    pub fn decimal_places(&self) -> u32 {
+        match self {
+            Currency::Brl | Currency::Usd => 2,
+            Currency::Jpy => 0,
+            Currency::Generic => 3,
+        }
+    }
+
+    /// Quantidade de casas decimais usada ao **exibir** um valor nesta
+    /// moeda.
+    ///
+    /// Para `Generic` isso é 2, preservando o comportamento histórico da
+    /// ferramenta, mesmo a escala interna sendo mais fina (`decimal_places`,
+    /// usada para não perder precisão em conversões de taxa de câmbio).
+    pub fn display_places(&self) -> u32 {
+        match self {
+            Currency::Generic => 2,
+            _ => self.decimal_places(),
+        }
+    }
+
+    /// Código usado para representar esta moeda na CLI (ISO 4217, quando
+    /// aplicável).
+    pub fn code(&self) -> &'static str {
+        match self {
+            Currency::Brl => "BRL",
+            Currency::Usd => "USD",
+            Currency::Jpy => "JPY",
+            Currency::Generic => "",
+        }
+    }
+}
+
+impl Default for Currency {
+    /// A moeda usada quando nenhum sufixo é informado na CLI.
+    fn default() -> Self {
+        Currency::Generic
+    }
+}
+
+impl FromStr for Currency {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_uppercase().as_str() {
+            "BRL" => Ok(Currency::Brl),
+            "USD" => Ok(Currency::Usd),
+            "JPY" => Ok(Currency::Jpy),
+            _ => Err(format!("moeda desconhecida: {s}")),
+        }
+    }
+}
+
+/// Estratégia de arredondamento usada ao colapsar um valor exato de
+/// [`Money`] na menor unidade concreta da sua moeda.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RoundStrategy {
+    /// Arredondamento bancário: metade sempre arredonda para o par mais
+    /// próximo, reduzindo o viés ao somar muitos valores arredondados.
+    Banker,
+    /// Sempre arredonda para cima (em direção a +infinito).
+    Up,
+    /// Sempre arredonda para baixo (em direção a -infinito).
+    Down,
+    /// Metade sempre arredonda para longe de zero — o comportamento do
+    /// `.round()` de ponto flutuante usado originalmente por esta
+    /// ferramenta.
+    HalfUp,
+}
+
+impl Default for RoundStrategy {
+    /// A estratégia usada quando nenhuma é informada na CLI, preservando o
+    /// comportamento original de `Money::from`.
+    fn default() -> Self {
+        RoundStrategy::HalfUp
+    }
+}
+
+impl FromStr for RoundStrategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "banker" => Ok(RoundStrategy::Banker),
+            "up" => Ok(RoundStrategy::Up),
+            "down" => Ok(RoundStrategy::Down),
+            "half-up" => Ok(RoundStrategy::HalfUp),
+            _ => Err(format!("modo de arredondamento desconhecido: {s}")),
+        }
+    }
+}
+
+/// Fração exata e sempre reduzida (denominador positivo), usada
+/// internamente para manter a precisão dos cálculos de [`Money`] — em
+/// especial divisões e rateios — até o momento em que o valor precisa ser
+/// colapsado em uma quantidade concreta de unidades monetárias.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Rational {
+    numer: i128,
+    denom: i128,
+}
+
+impl Rational {
+    fn new(numer: i128, denom: i128) -> Self {
+        assert!(denom != 0, "denominador não pode ser zero");
+        let sign = if denom < 0 { -1 } else { 1 };
+        let (numer, denom) = (numer * sign, denom * sign);
+        let divisor = gcd(numer.unsigned_abs(), denom.unsigned_abs()).max(1) as i128;
+        Self {
+            numer: numer / divisor,
+            denom: denom / divisor,
+        }
+    }
+
+    fn from_integer(value: i128) -> Self {
+        Self { numer: value, denom: 1 }
+    }
+
+    /// Aproxima um `f64` como fração.
+    ///
+    /// Inteiros são representados de forma exata. Valores fracionários são
+    /// aproximados com um denominador fixo de 1e9 — o suficiente para as
+    /// taxas de conversão e proporções usadas pela ferramenta, mas não uma
+    /// fração verdadeiramente exata do `f64` de entrada: o resultado só é
+    /// exato até 1e-9 do valor original.
+    fn from_f64(value: f64) -> Self {
+        const PRECISION: i128 = 1_000_000_000;
+        if value.fract() == 0.0 {
+            Self::from_integer(value as i128)
+        } else {
+            Self::new((value * PRECISION as f64).round() as i128, PRECISION)
+        }
+    }
+
+    fn to_f64(self) -> f64 {
+        self.numer as f64 / self.denom as f64
+    }
+
+    fn add(self, rhs: Self) -> Self {
+        Self::new(
+            self.numer * rhs.denom + rhs.numer * self.denom,
+            self.denom * rhs.denom,
+        )
+    }
+
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(
+            self.numer * rhs.denom - rhs.numer * self.denom,
+            self.denom * rhs.denom,
+        )
+    }
+
+    fn mul(self, rhs: Self) -> Self {
+        Self::new(self.numer * rhs.numer, self.denom * rhs.denom)
+    }
+
+    fn div(self, rhs: Self) -> Self {
+        Self::new(self.numer * rhs.denom, self.denom * rhs.numer)
+    }
+
+    /// Maior inteiro menor ou igual à fração (piso).
+    fn floor(self) -> i128 {
+        self.numer.div_euclid(self.denom)
+    }
+
+    /// Menor inteiro maior ou igual à fração (teto).
+    fn ceil(self) -> i128 {
+        let floor = self.floor();
+        if Self::from_integer(floor) == self {
+            floor
+        } else {
+            floor + 1
+        }
+    }
+
+    /// Arredonda para o inteiro mais próximo, com metade arredondando para
+    /// longe de zero (mesma convenção do `.round()` de ponto flutuante).
+    fn round_half_away_from_zero(self) -> i128 {
+        let sign: i128 = if self.numer < 0 { -1 } else { 1 };
+        let abs_numer = self.numer.unsigned_abs();
+        let denom = self.denom.unsigned_abs();
+        let quotient = abs_numer / denom;
+        let remainder = abs_numer % denom;
+        let rounded = if remainder * 2 >= denom {
+            quotient + 1
+        } else {
+            quotient
+        };
+        sign * rounded as i128
+    }
+
+    /// Arredonda para o inteiro mais próximo, com metade arredondando para
+    /// o par mais próximo (arredondamento bancário).
+    fn round_half_to_even(self) -> i128 {
+        let floor = self.floor();
+        let fraction = self.sub(Self::from_integer(floor));
+        match fraction.cmp(&Self::new(1, 2)) {
+            Ordering::Less => floor,
+            Ordering::Greater => floor + 1,
+            Ordering::Equal if floor % 2 == 0 => floor,
+            Ordering::Equal => floor + 1,
+        }
+    }
+
+    /// Colapsa a fração para um inteiro segundo a [`RoundStrategy`] dada.
+    fn collapse(self, strategy: RoundStrategy) -> i128 {
+        match strategy {
+            RoundStrategy::Down => self.floor(),
+            RoundStrategy::Up => self.ceil(),
+            RoundStrategy::HalfUp => self.round_half_away_from_zero(),
+            RoundStrategy::Banker => self.round_half_to_even(),
+        }
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Rational {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.numer * other.denom).cmp(&(other.numer * self.denom))
+    }
+}
+
+fn gcd(a: u128, b: u128) -> u128 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/// Representa um valor monetário em uma moeda específica.
+///
+/// `Money` mantém o valor internamente como uma fração (ver [`Rational`]).
+/// O valor é colapsado para a menor unidade concreta da moeda ao ser criado
+/// a partir de um número decimal ([`Money::with_rounding`]), ao ser exibido
+/// e ao emitir um pagamento; o resultado de multiplicações e divisões
+/// permanece uma fração exata. Isso evita o erro de arredondamento
+/// acumulado que surgiria ao rotear divisões por `f64` — ainda que a
+/// fração em si só seja exata para valores inteiros; a aproximação de um
+/// `f64` fracionário (ver [`Rational::from_f64`]) carrega seu próprio
+/// limite de precisão de 1e-9, irrelevante na prática para valores
+/// monetários.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Money {
+    value: Rational,
+    currency: Currency,
+}
 
 impl Money {
+    /// Cria um valor monetário a partir de um número decimal e sua moeda,
+    /// usando [`RoundStrategy::default`] para colapsar a menor unidade.
+    pub fn new(value: f64, currency: Currency) -> Self {
+        Money::with_rounding(value, currency, RoundStrategy::default())
+    }
+
+    /// Cria um valor monetário a partir de um número decimal, colapsando-o
+    /// para a menor unidade concreta da moeda com a estratégia de
+    /// arredondamento informada.
+    pub fn with_rounding(value: f64, currency: Currency, strategy: RoundStrategy) -> Self {
+        let scale = 10f64.powi(currency.decimal_places() as i32);
+        let minor_units = Rational::from_f64(value * scale).collapse(strategy);
+        Self {
+            value: Rational::from_integer(minor_units),
+            currency,
+        }
+    }
+
+    /// Retorna a moeda deste valor.
+    pub fn currency(&self) -> Currency {
+        self.currency
+    }
+
     /// Retorna o valor como um número decimal (em reais/dólares/etc.).
     pub fn decimal(&self) -> f64 {
-        self.0 as f64 / 1000.
+        let scale = 10f64.powi(self.currency().decimal_places() as i32);
+        self.value.to_f64() / scale
+    }
+
+    /// Retorna se este valor é exatamente zero, independente da moeda.
+    pub fn is_zero(&self) -> bool {
+        self.value == Rational::from_integer(0)
+    }
+
+    fn from_minor_units(units: i128, currency: Currency) -> Self {
+        Self {
+            value: Rational::from_integer(units),
+            currency,
+        }
+    }
+
+    /// Garante que `self` e `other` estão na mesma moeda antes de uma
+    /// operação aritmética, evitando somar/subtrair valores incompatíveis.
+    fn assert_same_currency(&self, other: &Self, op: &str) {
+        assert_eq!(
+            self.currency, other.currency,
+            "não é possível {op} valores em moedas diferentes ({} e {})",
+            self.currency.code(),
+            other.currency.code()
+        );
     }
 }
 
@@ -21,14 +337,84 @@ where
     T: Into<f64>,
 {
     fn from(value: T) -> Self {
-        let cents: f64 = (value.into() * 1000.).round();
-        Self(cents as usize)
+        Money::new(value.into(), Currency::default())
     }
 }
 
+/// Distribui `total` proporcionalmente aos pesos em `weights` usando o
+/// método do maior resto (_largest remainder method_), garantindo que a
+/// soma das partes devolvidas seja exatamente igual a `total` quando este é
+/// um número inteiro de unidades menores (como todo valor criado por
+/// [`Money::new`]); uma fração de unidade em `total` é descartada.
+///
+/// Cada parte é calculada como uma fração ideal exata e arredondada para
+/// baixo; o resto resultante (na menor unidade da moeda de `total`) é
+/// distribuído uma unidade de cada vez às chaves com a maior fração
+/// restante, usando a própria chave como critério de desempate estável.
+/// Isso torna o resultado determinístico independentemente da ordem de
+/// iteração de `weights` (ex: vinda de um `HashMap`).
+///
+/// O piso usado aqui é sempre o piso aritmético, não a [`RoundStrategy`]
+/// configurada pelo usuário: é o piso (e a distribuição do resto) que
+/// garante a soma exata, não uma escolha de arredondamento institucional.
+/// `RoundStrategy` entra em jogo apenas ao colapsar valores que ainda
+/// carregam uma fração fora desse processo (ex: [`Money::with_rounding`]).
+///
+/// # Panics
+///
+/// Entra em pânico se a soma dos pesos for zero.
+pub fn allocate(total: Money, weights: &[(String, u32)]) -> HashMap<String, Money> {
+    let weight_sum: u32 = weights.iter().map(|(_, weight)| *weight).sum();
+    assert!(weight_sum > 0, "a soma dos pesos deve ser maior que zero");
+
+    let mut minor_units: HashMap<String, i128> = HashMap::new();
+    let mut fractions: Vec<(String, Rational)> = Vec::new();
+
+    for (key, weight) in weights {
+        let ideal_share = total
+            .value
+            .mul(Rational::new(*weight as i128, weight_sum as i128));
+        let floor = ideal_share.floor();
+
+        minor_units.insert(key.clone(), floor);
+        fractions.push((key.clone(), ideal_share.sub(Rational::from_integer(floor))));
+    }
+
+    let floor_sum: i128 = minor_units.values().sum();
+    let leftover = (total.value.floor() - floor_sum).max(0) as usize;
+
+    fractions.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    for (key, _) in fractions.into_iter().take(leftover) {
+        *minor_units.get_mut(&key).unwrap() += 1;
+    }
+
+    minor_units
+        .into_iter()
+        .map(|(key, units)| (key, Money::from_minor_units(units, total.currency)))
+        .collect()
+}
+
 impl fmt::Display for Money {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{:.2}", self.decimal())
+        write!(
+            f,
+            "{:.*}",
+            self.currency.display_places() as usize,
+            self.decimal()
+        )
+    }
+}
+
+impl PartialOrd for Money {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Money {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.assert_same_currency(other, "comparar");
+        self.value.cmp(&other.value)
     }
 }
 
@@ -37,13 +423,17 @@ impl Add for Money {
     type Output = Self;
 
     fn add(self, rhs: Self) -> Self::Output {
-        Self(self.0 + rhs.0)
+        self.assert_same_currency(&rhs, "somar");
+        Self {
+            value: self.value.add(rhs.value),
+            currency: self.currency,
+        }
     }
 }
 
 impl AddAssign for Money {
     fn add_assign(&mut self, rhs: Self) {
-        self.0 += rhs.0
+        *self = *self + rhs;
     }
 }
 
@@ -54,14 +444,19 @@ where
     type Output = Money;
 
     fn add(self, rhs: T) -> Self::Output {
-        let rhs_money = Money::from(rhs);
-        Money(self.0 + rhs_money.0)
+        self + Money::new(rhs.into(), self.currency)
     }
 }
 
 impl Sum for Money {
     fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
-        iter.fold(Money(0), |acc, x| Money(acc.0 + x.0))
+        iter.fold(None, |acc: Option<Money>, x| {
+            Some(match acc {
+                Some(acc) => acc + x,
+                None => x,
+            })
+        })
+        .unwrap_or_else(|| Money::new(0., Currency::default()))
     }
 }
 
@@ -69,7 +464,11 @@ impl Sum for Money {
 impl Sub for Money {
     type Output = Self;
     fn sub(self, rhs: Self) -> Self::Output {
-        Self(self.0 - rhs.0)
+        self.assert_same_currency(&rhs, "subtrair");
+        Self {
+            value: self.value.sub(rhs.value),
+            currency: self.currency,
+        }
     }
 }
 
@@ -79,8 +478,7 @@ where
 {
     type Output = Money;
     fn sub(self, rhs: T) -> Self::Output {
-        let rhs_money = Money::from(rhs);
-        Money(self.0 - rhs_money.0)
+        self - Money::new(rhs.into(), self.currency)
     }
 }
 
@@ -91,7 +489,10 @@ where
 {
     type Output = Money;
     fn mul(self, rhs: T) -> Self::Output {
-        (self.decimal() * rhs.into()).into()
+        Money {
+            value: self.value.mul(Rational::from_f64(rhs.into())),
+            currency: self.currency,
+        }
     }
 }
 
@@ -102,7 +503,10 @@ where
 {
     type Output = Money;
     fn div(self, rhs: T) -> Self::Output {
-        (self.decimal() / rhs.into()).into()
+        Money {
+            value: self.value.div(Rational::from_f64(rhs.into())),
+            currency: self.currency,
+        }
     }
 }
 
@@ -111,7 +515,7 @@ where
     T: Into<f64>,
 {
     fn div_assign(&mut self, rhs: T) {
-        *self = Money(self.0 + (self.decimal() / rhs.into()) as usize);
+        *self = *self / rhs;
     }
 }
 
@@ -121,24 +525,22 @@ mod test {
 
     #[test]
     fn test_create_money_from_numbers() {
-        assert_eq!(Money::from(20), Money(20000));
-        assert_eq!(Money::from(139.94), Money(139940));
+        assert_eq!(Money::from(20), Money::new(20., Currency::default()));
+        assert_eq!(Money::from(139.94), Money::new(139.94, Currency::default()));
     }
 
     #[test]
     fn test_from_rounds_correctly() {
-        assert_eq!(Money::from(10.5556), Money(10556));
-        assert_eq!(Money::from(10.5554), Money(10555));
+        assert_eq!(Money::from(10.5556).decimal(), 10.556);
+        assert_eq!(Money::from(10.5554).decimal(), 10.555);
     }
 
     #[test]
     fn test_read_money() {
         let m = Money::from(20);
-        assert_eq!(m.0, 20000);
         assert_eq!(m.decimal(), 20.0);
 
         let m = Money::from(19.952);
-        assert_eq!(m.0, 19952);
         assert_eq!(m.decimal(), 19.952);
     }
 
@@ -165,4 +567,86 @@ mod test {
         assert_eq!(Money::from(30) / 2, Money::from(15));
         assert_eq!(Money::from(30) / 1.5, Money::from(20));
     }
+
+    #[test]
+    fn test_currency_decimal_places() {
+        assert_eq!(Money::new(10.555, Currency::Brl).decimal(), 10.56);
+        assert_eq!(Money::new(10.0, Currency::Jpy).decimal(), 10.0);
+    }
+
+    #[test]
+    fn test_currency_from_str() {
+        assert_eq!("usd".parse::<Currency>(), Ok(Currency::Usd));
+        assert_eq!("BRL".parse::<Currency>(), Ok(Currency::Brl));
+        assert!("XYZ".parse::<Currency>().is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "moedas diferentes")]
+    fn test_add_rejects_different_currencies() {
+        let _ = Money::new(10., Currency::Brl) + Money::new(10., Currency::Usd);
+    }
+
+    #[test]
+    fn test_default_currency_displays_with_two_decimals() {
+        // `Generic` guarda décimos de centavo internamente (para não perder
+        // precisão em conversões), mas a exibição ao usuário preserva as 2
+        // casas decimais históricas da ferramenta.
+        assert_eq!(Money::from(29.6667).to_string(), "29.67");
+        assert_eq!(Money::from(10.166).to_string(), "10.17");
+    }
+
+    #[test]
+    fn test_allocate_distributes_leftover_by_largest_remainder() {
+        // R$10.00 / 3 = R$3.333... cada; sobra 1 centavo, e o empate entre
+        // os restos iguais é resolvido pela chave (ordem alfabética).
+        let weights = vec![
+            ("A".to_string(), 1),
+            ("B".to_string(), 1),
+            ("C".to_string(), 1),
+        ];
+        let shares = allocate(Money::new(10., Currency::Brl), &weights);
+
+        let total: Money = shares.values().copied().sum();
+        assert_eq!(total, Money::new(10., Currency::Brl));
+        assert_eq!(shares[&"A".to_string()], Money::new(3.34, Currency::Brl));
+        assert_eq!(shares[&"B".to_string()], Money::new(3.33, Currency::Brl));
+        assert_eq!(shares[&"C".to_string()], Money::new(3.33, Currency::Brl));
+    }
+
+    #[test]
+    fn test_allocate_scales_by_weight() {
+        let weights = vec![("solo".to_string(), 1), ("grupo".to_string(), 2)];
+        let shares = allocate(Money::new(9., Currency::Brl), &weights);
+
+        assert_eq!(shares[&"solo".to_string()], Money::new(3., Currency::Brl));
+        assert_eq!(shares[&"grupo".to_string()], Money::new(6., Currency::Brl));
+    }
+
+    #[test]
+    fn test_rounding_strategies() {
+        assert_eq!(
+            Money::with_rounding(10.125, Currency::Brl, RoundStrategy::HalfUp).decimal(),
+            10.13
+        );
+        assert_eq!(
+            Money::with_rounding(10.125, Currency::Brl, RoundStrategy::Banker).decimal(),
+            10.12
+        );
+        assert_eq!(
+            Money::with_rounding(10.121, Currency::Brl, RoundStrategy::Up).decimal(),
+            10.13
+        );
+        assert_eq!(
+            Money::with_rounding(10.129, Currency::Brl, RoundStrategy::Down).decimal(),
+            10.12
+        );
+    }
+
+    #[test]
+    fn test_rounding_from_str() {
+        assert_eq!("half-up".parse::<RoundStrategy>(), Ok(RoundStrategy::HalfUp));
+        assert_eq!("Banker".parse::<RoundStrategy>(), Ok(RoundStrategy::Banker));
+        assert!("diagonal".parse::<RoundStrategy>().is_err());
+    }
 }
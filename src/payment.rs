@@ -6,7 +6,7 @@ use petgraph::prelude::StableDiGraph;
 use petgraph::visit::IntoEdgeReferences;
 use petgraph::visit::{EdgeRef, IntoNodeReferences, NodeRef};
 
-use crate::money::Money;
+use crate::money::{self, Money};
 use crate::person::Person;
 
 /// Representa uma transação única de pagamento entre duas pessoas.
@@ -77,31 +77,30 @@ impl Payments {
     fn simplify_bidirectional_edges(&mut self) {
         let indexes = self.0.edge_indices().collect::<Vec<_>>();
         for edge in indexes {
-            if let Some((source, target)) = self.0.edge_endpoints(edge) {
-                if let Some(e2) = self.0.find_edge(target, source)
-                    && let Some(e1) = self.0.find_edge(source, target)
-                {
-                    let w1 = self.0.edge_weight(e1).unwrap();
-                    let w2 = self.0.edge_weight(e2).unwrap();
-
-                    match w1.cmp(w2) {
-                        Ordering::Less => {
-                            // Aresta A -> B é removida
-                            // Aresta B -> A é atualizada com a diferença
-                            self.0.update_edge(target, source, *w2 - *w1);
-                            self.0.remove_edge(e1);
-                        }
-                        Ordering::Greater => {
-                            // Aresta A -> B é atualizada com a diferença
-                            // Aresta B -> A é removida
-                            self.0.update_edge(source, target, *w1 - *w2);
-                            self.0.remove_edge(e2);
-                        }
-                        Ordering::Equal => {
-                            // Dívidas se anulam
-                            self.0.remove_edge(e1);
-                            self.0.remove_edge(e2);
-                        }
+            if let Some((source, target)) = self.0.edge_endpoints(edge)
+                && let Some(e2) = self.0.find_edge(target, source)
+                && let Some(e1) = self.0.find_edge(source, target)
+            {
+                let w1 = self.0.edge_weight(e1).unwrap();
+                let w2 = self.0.edge_weight(e2).unwrap();
+
+                match w1.cmp(w2) {
+                    Ordering::Less => {
+                        // Aresta A -> B é removida
+                        // Aresta B -> A é atualizada com a diferença
+                        self.0.update_edge(target, source, *w2 - *w1);
+                        self.0.remove_edge(e1);
+                    }
+                    Ordering::Greater => {
+                        // Aresta A -> B é atualizada com a diferença
+                        // Aresta B -> A é removida
+                        self.0.update_edge(source, target, *w1 - *w2);
+                        self.0.remove_edge(e2);
+                    }
+                    Ordering::Equal => {
+                        // Dívidas se anulam
+                        self.0.remove_edge(e1);
+                        self.0.remove_edge(e2);
                     }
                 }
             }
@@ -193,44 +192,45 @@ impl Payments {
         println!("{dot}");
     }
 
-    /// Verifica se os pagamentos estão consistentes dentro de um limite de tolerância.
-    ///
-    /// Calcula o valor médio que cada pessoa deveria ter pago e compara com o saldo
-    /// final de cada participante (considerando o que gastou, pagou e recebeu).
+    /// Imprime a lista de pagamentos em formato de texto legível na saída padrão.
+    pub fn print_text(&self) {
+        for payment in self.to_vec() {
+            println!("{} deve pagar {} para {}", payment.from, payment.to, payment.value);
+        }
+    }
+
+    /// Saldo líquido de `person` neste grafo: quanto recebeu menos quanto pagou.
+    fn net_balance(&self, person: &Person) -> Money {
+        let payments = self.to_vec();
+        let to_receive: Money = payments
+            .iter()
+            .filter(|p| &p.to == person)
+            .map(|p| p.value)
+            .sum();
+        let to_pay: Money = payments
+            .iter()
+            .filter(|p| &p.from == person)
+            .map(|p| p.value)
+            .sum();
+        to_receive - to_pay
+    }
+
+    /// Verifica se os pagamentos fecham a conta exatamente.
     ///
-    /// Aceita pequenas diferenças de até '0,5 centavo * número de participantes'.
-    /// Retorna `true` se todos os saldos estiverem dentro desse limite.
+    /// Como a alocação de centavos ([`money::allocate`]) é feita de forma
+    /// independente para cada credor, não existe mais um valor único que
+    /// "cada pessoa deveria ter pago" a comparar: o arredondamento de cada
+    /// credor pode cair de um lado ou de outro. Em vez disso, reconstrói o
+    /// grafo de pagamentos original (sem as simplificações de
+    /// [`Self::optimize`]) a partir das mesmas pessoas e garante que o saldo
+    /// líquido de cada uma não muda — ou seja, que simplificar as dívidas não
+    /// alterou quem deve receber ou pagar quanto.
     pub fn validate(&self) -> bool {
-        let payments = self.to_vec();
-        let persons = self.get_persons();
+        let unsimplified: Payments = self.get_persons().into_iter().collect();
 
-        let num_persons: u32 = persons.iter().map(|p| p.size()).sum();
-        let total_debt: Money = persons.iter().map(|p| p.money_spent()).sum();
-        let amount_for_each = total_debt / num_persons;
-
-        for person in persons {
-            let to_receive: Money = payments
-                .iter()
-                .filter(|p| p.to == person)
-                .map(|p| p.value)
-                .sum();
-            let to_pay: Money = payments
-                .iter()
-                .filter(|p| p.from == person)
-                .map(|p| p.value)
-                .sum();
-
-            let final_balance = (person.money_spent() + to_pay - to_receive) / person.size();
-
-            // Verifica se a diferença está dentro do limite de tolerância.
-            // O limite é definido como 0.5 centavos multiplicado pelo número total de pessoas,
-            // permitindo uma margem de erro proporcional ao tamanho do grupo.
-            let diff = (amount_for_each.decimal() - final_balance.decimal()).abs();
-            if diff >= 0.005 * num_persons as f64 {
-                return false;
-            }
-        }
-        true
+        self.get_persons()
+            .iter()
+            .all(|person| self.net_balance(person) == unsimplified.net_balance(person))
     }
 }
 
@@ -239,20 +239,24 @@ impl FromIterator<Person> for Payments {
         let persons: Vec<Person> = iter.into_iter().collect();
         let mut payments = Vec::new();
 
-        let num_persons: u32 = persons.iter().map(|p| p.size()).sum();
+        // As chaves são o identificador de cada participante, não a posição
+        // na lista, para que o rateio seja determinístico mesmo vindo de um
+        // `HashMap` com ordem de iteração arbitrária.
+        let weights: Vec<(String, u32)> = persons
+            .iter()
+            .map(|p| (p.identifier(), p.size()))
+            .collect();
 
         for creditor in persons.iter() {
             if matches!(creditor, Person::Unnamed { .. })
-                || matches!(creditor, Person::Named { money_spent, .. } if money_spent.cents() == 0)
+                || matches!(creditor, Person::Named { money_spent, .. } if money_spent.is_zero())
             {
                 continue;
             }
 
-            let amount_for_each = creditor.money_spent() / num_persons as f64;
+            let shares = money::allocate(creditor.money_spent(), &weights);
             for debitor in persons.iter().filter(|p| p != &creditor) {
-                let amount = amount_for_each * debitor.size();
-
-                payments.push(Payment::new(debitor, creditor, amount));
+                payments.push(Payment::new(debitor, creditor, shares[&debitor.identifier()]));
             }
         }
 
@@ -317,4 +321,41 @@ mod test {
         assert_eq!(left, right);
         assert!(initial_payments.validate());
     }
+
+    #[test]
+    fn uneven_split_keeps_payments_balanced() {
+        // R$10 dividido por 3 pessoas não é exato; o método do maior resto
+        // deve, ainda assim, fechar a conta perfeitamente: A fica com 3.334 e
+        // recebe exatamente o restante (3.333 de B e 3.333 de C).
+        let persons = vec![
+            Person::named("A", 10.into()),
+            Person::named("B", 0.into()),
+            Person::named("C", 0.into()),
+        ];
+
+        let payments: Payments = persons.clone().into_iter().collect();
+
+        assert_eq!(payments.net_balance(&persons[0]), Money::from(6.666));
+        assert_eq!(payments.net_balance(&persons[1]), Money::from(-3.333));
+        assert_eq!(payments.net_balance(&persons[2]), Money::from(-3.333));
+        assert!(payments.validate());
+    }
+
+    #[test]
+    fn independent_creditor_rounding_still_validates() {
+        // Cada credor aloca as unidades de forma independente; aqui os dois
+        // dão a unidade que sobra para A (desempate pela chave), e C paga
+        // 3.333 para A e 2.333 para B — o saldo de C ainda precisa fechar
+        // exatamente.
+        let persons = vec![
+            Person::named("A", 10.into()),
+            Person::named("B", 7.into()),
+            Person::named("C", 0.into()),
+        ];
+
+        let payments: Payments = persons.clone().into_iter().collect();
+
+        assert_eq!(payments.net_balance(&persons[2]), Money::from(-5.666));
+        assert!(payments.validate());
+    }
 }
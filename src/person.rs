@@ -1,7 +1,7 @@
 use std::fmt;
 use std::hash::Hash;
 
-use rust_decimal::Decimal;
+use crate::money::Money;
 
 /// Representa um participante na divisão da conta.
 ///
@@ -10,17 +10,17 @@ use rust_decimal::Decimal;
 #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum Person {
     /// Uma pessoa específica que pagou um valor.
-    Named { name: String, money_spent: Decimal },
+    Named { name: String, money_spent: Money },
     /// Um grupo de pessoas que não pagaram.
     /// `size` é o número de pessoas neste grupo (ex: 3 pessoas).
     Unnamed { size: u32 },
 }
 
 impl Person {
-    pub fn named(name: &str, money_spent: Decimal) -> Self {
+    pub fn named(name: &str, money_spent: Money) -> Self {
         Person::Named {
             name: name.into(),
-            money_spent: money_spent.round_dp(2),
+            money_spent,
         }
     }
 
@@ -39,10 +39,10 @@ impl Person {
     }
 
     /// Retorna o valor total que esta entidade pagou inicialmente.
-    pub fn money_spent(&self) -> Decimal {
+    pub fn money_spent(&self) -> Money {
         match self {
             Person::Named { money_spent, .. } => *money_spent,
-            Person::Unnamed { .. } => 0.into(),
+            Person::Unnamed { .. } => Money::from(0),
         }
     }
 